@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_visit::VisitMut;
+
+/// Replaces default imports of local assets with a `const`. Assets at or
+/// below the limit are bound to a base64 data URI; larger ones to a
+/// `new URL(specifier, import.meta.url).href` file reference the outer bundler
+/// can emit as a file.
+pub struct InlineAssets {
+    base_dir: PathBuf,
+    limit: u64,
+}
+
+impl InlineAssets {
+    pub fn new(base_dir: &Path, limit: u64) -> Self {
+        InlineAssets {
+            base_dir: base_dir.to_path_buf(),
+            limit,
+        }
+    }
+
+    fn asset_url(&self, specifier: &str) -> Option<Expr> {
+        if !specifier.starts_with("./") && !specifier.starts_with("../") {
+            return None;
+        }
+        let mime = mime_type(specifier)?;
+        let path = self.base_dir.join(specifier);
+        if fs::metadata(&path).ok()?.len() > self.limit {
+            return Some(file_reference(specifier));
+        }
+        let bytes = fs::read(&path).ok()?;
+        let uri = format!("data:{};base64,{}", mime, base64::encode(bytes));
+        Some(str_lit(uri))
+    }
+
+    fn inline(&self, import: &ImportDecl) -> Option<ModuleItem> {
+        if import.type_only {
+            return None;
+        }
+        let local = match import.specifiers.as_slice() {
+            [ImportSpecifier::Default(default)] => default.local.clone(),
+            _ => return None,
+        };
+        let url = self.asset_url(&import.src.value)?;
+
+        Some(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: import.span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(local.into()),
+                init: Some(Box::new(url)),
+                definite: false,
+            }],
+        })))))
+    }
+}
+
+impl VisitMut for InlineAssets {
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        for item in items.iter_mut() {
+            let replacement = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => self.inline(import),
+                _ => None,
+            };
+            if let Some(replacement) = replacement {
+                *item = replacement;
+            }
+        }
+    }
+}
+
+fn str_lit(value: String) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span: DUMMY_SP,
+        value: value.into(),
+        raw: None,
+    }))
+}
+
+fn member(obj: Expr, prop: &str) -> Expr {
+    Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(obj),
+        prop: MemberProp::Ident(Ident::new(prop.into(), DUMMY_SP)),
+    })
+}
+
+// new URL("./logo.png", import.meta.url).href
+fn file_reference(specifier: &str) -> Expr {
+    let import_meta = Expr::MetaProp(MetaPropExpr {
+        span: DUMMY_SP,
+        kind: MetaPropKind::ImportMeta,
+    });
+    let url = Expr::New(NewExpr {
+        span: DUMMY_SP,
+        callee: Box::new(Expr::Ident(Ident::new("URL".into(), DUMMY_SP))),
+        args: Some(vec![
+            str_lit(specifier.to_string()).into(),
+            member(import_meta, "url").into(),
+        ]),
+        type_args: None,
+    });
+    member(url, "href")
+}
+
+fn mime_type(specifier: &str) -> Option<&'static str> {
+    let ext = Path::new(specifier).extension()?.to_str()?;
+    let mime = match ext.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{parse, print};
+    use swc_ecma_visit::VisitMutWith;
+
+    fn inline(src: &str, limit: u64) -> String {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let mut module = parse(src);
+        module.visit_mut_with(&mut InlineAssets::new(&fixtures, limit));
+        print(&module)
+    }
+
+    #[test]
+    fn inlines_asset_below_limit_as_data_uri() {
+        let out = inline("import pixel from './pixel.png';", 4096);
+        assert!(
+            out.contains(r#"const pixel = "data:image/png;base64,iVBORw0KGgo"#),
+            "{}",
+            out
+        );
+        assert!(!out.contains("import"), "{}", out);
+    }
+
+    #[test]
+    fn references_asset_above_limit_as_file() {
+        let out = inline("import pixel from './pixel.png';", 16);
+        assert!(
+            out.contains(r#"const pixel = new URL("./pixel.png", import.meta.url).href"#),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn leaves_type_only_and_unknown_imports_alone() {
+        let out = inline(
+            "import type Pixel from './pixel.png';\nimport data from './data.bin';",
+            4096,
+        );
+        assert!(!out.contains("const"), "{}", out);
+    }
+}
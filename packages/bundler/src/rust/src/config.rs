@@ -0,0 +1,42 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BundleConfig {
+    /// Assets at or below this size (in bytes) are inlined as data URIs;
+    /// larger ones become `new URL(..., import.meta.url)` file references.
+    pub inline_asset_limit: u64,
+    /// Minimum repetitions for a string/number literal to be hoisted into a
+    /// shared constant. `None` disables the pass.
+    pub hoist_constants: Option<usize>,
+    /// Modules larger than this many bytes of source produce a warning.
+    pub module_size_warning: u64,
+    /// Remove `console.*(...)` call statements.
+    pub drop_console: bool,
+    /// Remove `debugger` statements.
+    pub drop_debugger: bool,
+    /// Console methods kept when `drop_console` is set.
+    pub keep_console: Vec<String>,
+    /// Where to write the JSON catalog of translation keys used by the
+    /// module. `None` disables extraction.
+    pub i18n_catalog: Option<PathBuf>,
+    /// Translation functions whose first argument is a key, e.g. `t` or
+    /// `i18n.translate`.
+    pub i18n_functions: Vec<String>,
+}
+
+impl Default for BundleConfig {
+    fn default() -> Self {
+        BundleConfig {
+            inline_asset_limit: 4096,
+            hoist_constants: None,
+            module_size_warning: 512 * 1024,
+            drop_console: false,
+            drop_debugger: false,
+            keep_console: vec!["error".into(), "warn".into()],
+            i18n_catalog: None,
+            i18n_functions: vec!["t".into()],
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use swc_common::Span;
+use swc_ecma_ast::*;
+
+pub struct DuplicateExport {
+    pub name: String,
+    pub first: Span,
+    pub second: Span,
+}
+
+/// Finds export names declared more than once in a module, including via
+/// re-exports. `export *` is skipped since its names are only known after
+/// resolving the target module. Declarations TypeScript allows to repeat are
+/// not reported: function overload signatures, `declare` items, and merged
+/// enum declarations.
+pub fn duplicate_exports(module: &Module) -> Vec<DuplicateExport> {
+    let mut seen: HashMap<String, (Span, bool)> = HashMap::new();
+    let mut duplicates = Vec::new();
+
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::ModuleDecl(decl) => decl,
+            ModuleItem::Stmt(_) => continue,
+        };
+        let is_enum = matches!(
+            decl,
+            ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::TsEnum(_),
+                ..
+            })
+        );
+        for (name, span) in exported_names(decl) {
+            match seen.get(&name) {
+                Some(&(_, true)) if is_enum => {}
+                Some(&(first, _)) => duplicates.push(DuplicateExport {
+                    name,
+                    first,
+                    second: span,
+                }),
+                None => {
+                    seen.insert(name, (span, is_enum));
+                }
+            }
+        }
+    }
+
+    duplicates
+}
+
+fn exported_names(decl: &ModuleDecl) -> Vec<(String, Span)> {
+    let mut names = Vec::new();
+    match decl {
+        ModuleDecl::ExportDecl(export) => match &export.decl {
+            Decl::Class(class) if !class.declare => names.push(ident(&class.ident)),
+            Decl::Fn(func) if !func.declare && func.function.body.is_some() => {
+                names.push(ident(&func.ident))
+            }
+            Decl::Var(var) if !var.declare => {
+                for declarator in &var.decls {
+                    pat_names(&declarator.name, &mut names);
+                }
+            }
+            Decl::TsEnum(en) if !en.declare => names.push(ident(&en.id)),
+            _ => {}
+        },
+        ModuleDecl::ExportNamed(named) if !named.type_only => {
+            for specifier in &named.specifiers {
+                match specifier {
+                    ExportSpecifier::Named(s) if !s.is_type_only => {
+                        names.push(export_name(s.exported.as_ref().unwrap_or(&s.orig)));
+                    }
+                    ExportSpecifier::Default(s) => names.push(ident(&s.exported)),
+                    ExportSpecifier::Namespace(s) => names.push(export_name(&s.name)),
+                    _ => {}
+                }
+            }
+        }
+        ModuleDecl::ExportDefaultDecl(export) => match &export.decl {
+            DefaultDecl::Fn(func) if func.function.body.is_none() => {}
+            DefaultDecl::TsInterfaceDecl(_) => {}
+            _ => names.push(("default".into(), export.span)),
+        },
+        ModuleDecl::ExportDefaultExpr(export) => names.push(("default".into(), export.span)),
+        _ => {}
+    }
+    names
+}
+
+fn pat_names(pat: &Pat, names: &mut Vec<(String, Span)>) {
+    match pat {
+        Pat::Ident(binding) => names.push(ident(&binding.id)),
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                pat_names(elem, names);
+            }
+        }
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => pat_names(&kv.value, names),
+                    ObjectPatProp::Assign(assign) => names.push(ident(&assign.key)),
+                    ObjectPatProp::Rest(rest) => pat_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => pat_names(&rest.arg, names),
+        Pat::Assign(assign) => pat_names(&assign.left, names),
+        _ => {}
+    }
+}
+
+fn ident(id: &Ident) -> (String, Span) {
+    (id.sym.to_string(), id.span)
+}
+
+fn export_name(name: &ModuleExportName) -> (String, Span) {
+    match name {
+        ModuleExportName::Ident(id) => ident(id),
+        ModuleExportName::Str(s) => (s.value.to_string(), s.span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::parse;
+
+    fn duplicate_names(src: &str) -> Vec<String> {
+        duplicate_exports(&parse(src))
+            .into_iter()
+            .map(|duplicate| duplicate.name)
+            .collect()
+    }
+
+    #[test]
+    fn reports_name_exported_twice() {
+        assert_eq!(
+            duplicate_names("export const foo = 1;\nexport function foo() {}"),
+            ["foo"]
+        );
+        assert_eq!(
+            duplicate_names("const bar = 1;\nexport { bar as foo };\nexport { foo } from './foo';"),
+            ["foo"]
+        );
+    }
+
+    #[test]
+    fn allows_declarations_typescript_merges() {
+        let src = "
+            export function f(a: string): void;
+            export function f(a: any) {}
+            export enum E { A }
+            export enum E { B = 1 }
+            export declare const g: number;
+            export function g() {}
+            export default function h(a: string): void;
+            export default function h(a: any) {}
+        ";
+        assert!(duplicate_names(src).is_empty());
+    }
+
+    #[test]
+    fn reports_enum_clashing_with_value() {
+        assert_eq!(
+            duplicate_names("export enum E { A }\nexport const E = 1;"),
+            ["E"]
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::{HashMap, HashSet};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+const PREFIX: &str = "__onedot_c";
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Constant {
+    Str(String),
+    Num(u64),
+}
+
+impl Constant {
+    fn from_expr(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Lit(Lit::Str(s)) => Some(Constant::Str(s.value.to_string())),
+            Expr::Lit(Lit::Num(n)) => Some(Constant::Num(n.value.to_bits())),
+            _ => None,
+        }
+    }
+
+    fn to_expr(&self) -> Expr {
+        match self {
+            Constant::Str(value) => Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.clone().into(),
+                raw: None,
+            })),
+            Constant::Num(bits) => Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: f64::from_bits(*bits),
+                raw: None,
+            })),
+        }
+    }
+
+    fn source_len(&self) -> usize {
+        match self {
+            Constant::Str(value) => value.len() + 2,
+            Constant::Num(bits) => f64::from_bits(*bits).to_string().len(),
+        }
+    }
+}
+
+/// Hoists string and number literals that appear at least `threshold` times
+/// into `const` bindings at the top of the module and references those
+/// instead. Literals are only hoisted when the bytes saved across all
+/// references outweigh the added declaration, and hoisted names never reuse an
+/// identifier already present in the module.
+pub fn hoist_constants(module: &mut Module, threshold: usize) {
+    let mut counter = Counter::default();
+    module.visit_with(&mut counter);
+
+    let mut candidates: Vec<(Constant, usize)> = counter
+        .counts
+        .into_iter()
+        .filter(|(_, count)| *count >= threshold.max(2))
+        .collect();
+    candidates.sort();
+
+    let mut names = HashMap::new();
+    let mut next = 0;
+    for (constant, count) in candidates {
+        while counter.idents.contains(&format!("{}{}", PREFIX, next)) {
+            next += 1;
+        }
+        let name = format!("{}{}", PREFIX, next);
+        if saves_bytes(&constant, count, name.len()) {
+            names.insert(constant, Ident::new(name.into(), DUMMY_SP));
+            next += 1;
+        }
+    }
+    if names.is_empty() {
+        return;
+    }
+
+    module.visit_mut_with(&mut Replacer { names: &names });
+
+    let mut hoisted: Vec<_> = names.into_iter().collect();
+    hoisted.sort_by(|a, b| a.1.sym.cmp(&b.1.sym));
+    let decl = ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: hoisted
+            .into_iter()
+            .map(|(constant, name)| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(name.into()),
+                init: Some(Box::new(constant.to_expr())),
+                definite: false,
+            })
+            .collect(),
+    }))));
+
+    // Directives only take effect in the prologue, so the declaration goes
+    // after them as well as after the imports.
+    let prologue = module
+        .body
+        .iter()
+        .take_while(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(_)) => true,
+            ModuleItem::Stmt(Stmt::Expr(stmt)) => is_directive(stmt),
+            _ => false,
+        })
+        .count();
+    module.body.insert(prologue, decl);
+}
+
+// Each reference saves the difference in length; the declaration costs
+// roughly `name = literal` plus separators.
+fn saves_bytes(constant: &Constant, count: usize, name_len: usize) -> bool {
+    let literal_len = constant.source_len();
+    literal_len > name_len && count * (literal_len - name_len) > name_len + literal_len + 4
+}
+
+// Directives ("use strict") must stay literal.
+fn is_directive(stmt: &ExprStmt) -> bool {
+    matches!(&*stmt.expr, Expr::Lit(Lit::Str(_)))
+}
+
+// `import("./x")` and `require("./x")` specifiers must stay literal so they
+// remain statically analysable by downstream tooling.
+fn is_module_request(call: &CallExpr) -> bool {
+    match &call.callee {
+        Callee::Import(_) => true,
+        Callee::Expr(callee) => matches!(&**callee, Expr::Ident(id) if &*id.sym == "require"),
+        Callee::Super(_) => false,
+    }
+}
+
+// Likewise for `new URL("./x", import.meta.url)`, the asset and worker
+// reference form.
+fn is_file_reference(new: &NewExpr) -> bool {
+    let base = match new.args.as_deref() {
+        Some([_, base]) => &*base.expr,
+        _ => return false,
+    };
+    let is_import_meta_url = match base {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            &*prop.sym == "url"
+                && matches!(
+                    &**obj,
+                    Expr::MetaProp(MetaPropExpr {
+                        kind: MetaPropKind::ImportMeta,
+                        ..
+                    })
+                )
+        }
+        _ => false,
+    };
+    matches!(&*new.callee, Expr::Ident(id) if &*id.sym == "URL") && is_import_meta_url
+}
+
+#[derive(Default)]
+struct Counter {
+    counts: HashMap<Constant, usize>,
+    idents: HashSet<String>,
+}
+
+impl Visit for Counter {
+    fn visit_expr_stmt(&mut self, stmt: &ExprStmt) {
+        if !is_directive(stmt) {
+            stmt.visit_children_with(self);
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if is_module_request(call) {
+            return;
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, new: &NewExpr) {
+        if is_file_reference(new) {
+            return;
+        }
+        new.visit_children_with(self);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Some(constant) = Constant::from_expr(expr) {
+            *self.counts.entry(constant).or_default() += 1;
+        }
+        expr.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.idents.insert(ident.sym.to_string());
+    }
+}
+
+struct Replacer<'a> {
+    names: &'a HashMap<Constant, Ident>,
+}
+
+impl VisitMut for Replacer<'_> {
+    fn visit_mut_expr_stmt(&mut self, stmt: &mut ExprStmt) {
+        if !is_directive(stmt) {
+            stmt.visit_mut_children_with(self);
+        }
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        if is_module_request(call) {
+            return;
+        }
+        call.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_new_expr(&mut self, new: &mut NewExpr) {
+        if is_file_reference(new) {
+            return;
+        }
+        new.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if let Some(name) = Constant::from_expr(expr).and_then(|c| self.names.get(&c)) {
+            *expr = Expr::Ident(name.clone());
+            return;
+        }
+        expr.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{bundle_source, parse, print};
+    use crate::BundleConfig;
+
+    fn hoist(src: &str) -> String {
+        let mut module = parse(src);
+        hoist_constants(&mut module, 2);
+        print(&module)
+    }
+
+    #[test]
+    fn bundle_references_repeated_string_through_one_constant() {
+        let config = BundleConfig {
+            hoist_constants: Some(2),
+            ..BundleConfig::default()
+        };
+        let (result, diagnostics) = bundle_source(
+            "f(\"a fairly long repeated string\");\n\
+             g(\"a fairly long repeated string\");\n\
+             h(\"a fairly long repeated string\");\n",
+            &config,
+        );
+        let out = result.expect(&diagnostics);
+
+        assert_eq!(
+            out.matches("\"a fairly long repeated string\"").count(),
+            1,
+            "{}",
+            out
+        );
+        assert!(
+            out.starts_with("const __onedot_c0 = \"a fairly long repeated string\""),
+            "{}",
+            out
+        );
+        assert_eq!(out.matches("__onedot_c0").count(), 4, "{}", out);
+    }
+
+    #[test]
+    fn keeps_literal_when_declaration_costs_more() {
+        let out = hoist("f(\"abcdefghijklm\");\ng(\"abcdefghijklm\");");
+        assert!(!out.contains(PREFIX), "{}", out);
+    }
+
+    #[test]
+    fn inserts_after_directives_and_imports() {
+        let out = hoist(
+            "'use strict';\nimport x from './x';\n\
+             f('a fairly long repeated string', 'a fairly long repeated string', 'a fairly long repeated string');",
+        );
+        let decl = out.find("const __onedot_c0").unwrap();
+        assert!(out.find("use strict").unwrap() < decl, "{}", out);
+        assert!(out.find("import x").unwrap() < decl, "{}", out);
+    }
+
+    #[test]
+    fn keeps_file_reference_specifiers_literal() {
+        let src = "new Worker(new URL('./workers/image-decoder.js', import.meta.url));\n\
+                   new Worker(new URL('./workers/image-decoder.js', import.meta.url));\n\
+                   new Worker(new URL('./workers/image-decoder.js', import.meta.url));";
+        let out = hoist(src);
+        assert!(!out.contains(PREFIX), "{}", out);
+        assert_eq!(
+            out.matches("new URL('./workers/image-decoder.js', import.meta.url)")
+                .count(),
+            3,
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn skips_names_already_in_use() {
+        let out = hoist(
+            "const __onedot_c0 = 1;\n\
+             f(__onedot_c0, 'a fairly long repeated string', 'a fairly long repeated string', 'a fairly long repeated string');",
+        );
+        assert!(
+            out.contains("const __onedot_c1 = \"a fairly long repeated string\""),
+            "{}",
+            out
+        );
+        assert!(
+            out.contains("f(__onedot_c0, __onedot_c1, __onedot_c1, __onedot_c1)"),
+            "{}",
+            out
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use swc_common::{sync::Lrc, SourceMap, Span};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{EsConfig, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_visit::{Visit, VisitWith};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Translation key → every place it is used, ordered by key.
+pub type Catalog = BTreeMap<String, Vec<Location>>;
+
+/// Collects the string-literal first argument of calls to any of `functions`
+/// (plain names like `t` or member paths like `i18n.translate`) across
+/// `files`. Keys used in several places keep all their locations.
+pub fn extract_i18n(
+    files: &[&Path],
+    functions: &[&str],
+) -> Result<Catalog, Box<dyn std::error::Error>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let mut catalog = Catalog::new();
+
+    for file in files {
+        let fm = cm.load_file(file)?;
+        let mut parser = Parser::new(syntax_for(file), StringInput::from(&*fm), None);
+        let module = parser
+            .parse_module()
+            .map_err(|e| format!("{}: {}", file.display(), e.into_kind().msg()))?;
+
+        collect_keys(&module, functions, file, &cm, &mut catalog);
+    }
+
+    Ok(catalog)
+}
+
+/// Adds the keys used in an already parsed `module` to `catalog`.
+pub(crate) fn collect_keys(
+    module: &Module,
+    functions: &[&str],
+    file: &Path,
+    cm: &SourceMap,
+    catalog: &mut Catalog,
+) {
+    let mut collector = Collector {
+        functions,
+        keys: Vec::new(),
+    };
+    module.visit_with(&mut collector);
+
+    for (key, span) in collector.keys {
+        let loc = cm.lookup_char_pos(span.lo);
+        catalog.entry(key).or_default().push(Location {
+            file: file.display().to_string(),
+            line: loc.line,
+            column: loc.col.0 + 1,
+        });
+    }
+}
+
+// TypeScript for `.ts`/`.tsx`; anything else is parsed as JavaScript with JSX
+// enabled, since `.js` files commonly contain JSX too.
+fn syntax_for(file: &Path) -> Syntax {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("ts") | Some("mts") | Some("cts") => Syntax::Typescript(TsConfig::default()),
+        Some("tsx") => Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsConfig {
+            jsx: true,
+            ..Default::default()
+        }),
+    }
+}
+
+struct Collector<'a> {
+    functions: &'a [&'a str],
+    keys: Vec<(String, Span)>,
+}
+
+impl Visit for Collector<'_> {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let is_i18n_call = match &call.callee {
+            Callee::Expr(callee) => {
+                callee_path(callee).is_some_and(|path| self.functions.contains(&path.as_str()))
+            }
+            _ => false,
+        };
+        if is_i18n_call {
+            if let Some(key) = call.args.first().and_then(|arg| literal_key(&arg.expr)) {
+                self.keys.push(key);
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+fn callee_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(id) => Some(id.sym.to_string()),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(prop) => Some(format!("{}.{}", callee_path(&member.obj)?, prop.sym)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn literal_key(expr: &Expr) -> Option<(String, Span)> {
+    match expr {
+        Expr::Lit(Lit::Str(s)) => Some((s.value.to_string(), s.span)),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+            let quasi = tpl.quasis.first()?;
+            let cooked = quasi.cooked.as_ref()?;
+            Some((cooked.to_string(), tpl.span))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/i18n")
+            .join(name)
+    }
+
+    fn location(file: &Path, line: usize, column: usize) -> Location {
+        Location {
+            file: file.display().to_string(),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn extracts_keys_with_locations_across_files() {
+        let (ts, tsx, js) = (
+            fixture("home.ts"),
+            fixture("menu.tsx"),
+            fixture("footer.jsx"),
+        );
+        let catalog = extract_i18n(&[&ts, &tsx, &js], &["t", "i18n.translate"]).unwrap();
+
+        assert_eq!(
+            catalog.keys().collect::<Vec<_>>(),
+            [
+                "footer.copyright",
+                "home.title",
+                "menu.open",
+                "shared.close"
+            ]
+        );
+        assert_eq!(catalog["home.title"], [location(&ts, 3, 32)]);
+        assert_eq!(catalog["menu.open"], [location(&tsx, 4, 19)]);
+        assert_eq!(catalog["footer.copyright"], [location(&js, 2, 34)]);
+        assert_eq!(
+            catalog["shared.close"],
+            [
+                location(&ts, 4, 7),
+                location(&tsx, 5, 40),
+                location(&js, 4, 17),
+            ]
+        );
+    }
+}
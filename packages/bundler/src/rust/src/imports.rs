@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, HashSet};
+use swc_common::Span;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// Returns the local bindings introduced by import declarations that are never
+/// referenced elsewhere in the module, for reporting or removing them. Runs on
+/// the pre-strip AST, so a binding used only as a type still counts as
+/// referenced.
+pub fn unused_imports(module: &Module) -> Vec<Ident> {
+    let mut collector = References::default();
+    module.visit_with(&mut collector);
+
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(import),
+            _ => None,
+        })
+        .flat_map(|import| import.specifiers.iter())
+        .map(|specifier| match specifier {
+            ImportSpecifier::Named(s) => &s.local,
+            ImportSpecifier::Default(s) => &s.local,
+            ImportSpecifier::Namespace(s) => &s.local,
+        })
+        .filter(|local| !collector.referenced.contains(&*local.sym))
+        .cloned()
+        .collect()
+}
+
+/// `type` attribute values the loader knows how to handle.
+pub const KNOWN_IMPORT_TYPES: &[&str] = &["json", "css"];
+
+/// Import attributes of one import declaration, for routing it to a loader.
+#[derive(Debug, Clone)]
+pub struct ImportAttributes {
+    pub specifier: String,
+    pub attributes: BTreeMap<String, String>,
+    pub span: Span,
+}
+
+/// Returns the `assert { ... }` attributes of each import declaration that
+/// has them. Only string-valued attributes are captured. The pinned parser
+/// predates the `with { ... }` syntax, so such imports fail to parse rather
+/// than reaching this function.
+pub fn import_attributes(module: &Module) -> Vec<ImportAttributes> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(import),
+            _ => None,
+        })
+        .filter_map(|import| {
+            let asserts = import.asserts.as_ref()?;
+            let attributes = asserts
+                .props
+                .iter()
+                .filter_map(|prop| match prop {
+                    PropOrSpread::Prop(prop) => match &**prop {
+                        Prop::KeyValue(kv) => attribute(kv),
+                        _ => None,
+                    },
+                    PropOrSpread::Spread(_) => None,
+                })
+                .collect();
+            Some(ImportAttributes {
+                specifier: import.src.value.to_string(),
+                attributes,
+                span: asserts.span,
+            })
+        })
+        .collect()
+}
+
+fn attribute(kv: &KeyValueProp) -> Option<(String, String)> {
+    let key = match &kv.key {
+        PropName::Ident(id) => id.sym.to_string(),
+        PropName::Str(s) => s.value.to_string(),
+        _ => return None,
+    };
+    match &*kv.value {
+        Expr::Lit(Lit::Str(value)) => Some((key, value.value.to_string())),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct References {
+    referenced: HashSet<String>,
+}
+
+// Records identifiers in reference position only. Names that introduce a
+// binding (parameters, declarations, labels) or name a property are skipped,
+// so `function g(foo) {}` does not count as a use of an imported `foo`. Scopes
+// are not tracked: a shadowing binding that is itself used still hides an
+// unused import of the same name.
+impl Visit for References {
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+
+    // `a.foo` and `{ foo: 1 }` name properties, not bindings.
+    fn visit_member_prop(&mut self, prop: &MemberProp) {
+        if let MemberProp::Computed(computed) = prop {
+            computed.visit_with(self);
+        }
+    }
+
+    fn visit_super_prop(&mut self, prop: &SuperProp) {
+        if let SuperProp::Computed(computed) = prop {
+            computed.visit_with(self);
+        }
+    }
+
+    fn visit_prop_name(&mut self, name: &PropName) {
+        if let PropName::Computed(computed) = name {
+            computed.visit_with(self);
+        }
+    }
+
+    fn visit_private_name(&mut self, _: &PrivateName) {}
+
+    fn visit_binding_ident(&mut self, binding: &BindingIdent) {
+        binding.type_ann.visit_with(self);
+    }
+
+    // `{ foo = 1 } = obj` binds `foo`; only the default is a reference.
+    fn visit_assign_pat_prop(&mut self, prop: &AssignPatProp) {
+        prop.value.visit_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, decl: &FnDecl) {
+        decl.function.visit_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, expr: &FnExpr) {
+        expr.function.visit_with(self);
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDecl) {
+        decl.class.visit_with(self);
+    }
+
+    fn visit_class_expr(&mut self, expr: &ClassExpr) {
+        expr.class.visit_with(self);
+    }
+
+    fn visit_labeled_stmt(&mut self, stmt: &LabeledStmt) {
+        stmt.body.visit_with(self);
+    }
+
+    fn visit_break_stmt(&mut self, _: &BreakStmt) {}
+
+    fn visit_continue_stmt(&mut self, _: &ContinueStmt) {}
+
+    // `export { foo as bar }` uses `foo`; re-exports from another module
+    // reference nothing local.
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if export.src.is_none() {
+            export.visit_children_with(self);
+        }
+    }
+
+    fn visit_export_named_specifier(&mut self, specifier: &ExportNamedSpecifier) {
+        specifier.orig.visit_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, decl: &TsInterfaceDecl) {
+        decl.type_params.visit_with(self);
+        decl.extends.visit_with(self);
+        decl.body.visit_with(self);
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, decl: &TsTypeAliasDecl) {
+        decl.type_params.visit_with(self);
+        decl.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_enum_decl(&mut self, decl: &TsEnumDecl) {
+        for member in &decl.members {
+            member.init.visit_with(self);
+        }
+    }
+
+    fn visit_ts_type_param(&mut self, param: &TsTypeParam) {
+        param.constraint.visit_with(self);
+        param.default.visit_with(self);
+    }
+
+    fn visit_ts_property_signature(&mut self, signature: &TsPropertySignature) {
+        if signature.computed {
+            signature.key.visit_with(self);
+        }
+        signature.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_method_signature(&mut self, signature: &TsMethodSignature) {
+        if signature.computed {
+            signature.key.visit_with(self);
+        }
+        signature.params.visit_with(self);
+        signature.type_ann.visit_with(self);
+        signature.type_params.visit_with(self);
+    }
+
+    // Only the leftmost name of `ns.Foo` refers to a binding.
+    fn visit_ts_qualified_name(&mut self, name: &TsQualifiedName) {
+        name.left.visit_with(self);
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.referenced.insert(ident.sym.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::parse;
+
+    fn unused(src: &str) -> Vec<String> {
+        unused_imports(&parse(src))
+            .into_iter()
+            .map(|ident| ident.sym.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reports_unreferenced_named_import() {
+        assert_eq!(unused("import { foo, bar } from './m';\nbar();"), ["foo"]);
+    }
+
+    #[test]
+    fn keeps_used_and_namespace_imports() {
+        let src = "
+            import used from './a';
+            import * as ns from './b';
+            import { Props } from './c';
+            used(ns.x);
+            let props: Props;
+        ";
+        assert!(unused(src).is_empty());
+    }
+
+    #[test]
+    fn bindings_and_property_names_are_not_uses() {
+        let src = "
+            import { a, b, c, d, e, f, g, h } from './m';
+            function a(b) {}
+            const { c, x: d, e = 1 } = obj;
+            class f { g() {} }
+            obj.h;
+            interface I { a: string }
+        ";
+        assert_eq!(unused(src), ["a", "b", "c", "d", "e", "f", "g", "h"]);
+    }
+
+    #[test]
+    fn captures_import_attributes() {
+        let module = parse(
+            "import data from './data.json' assert { type: 'json', 'x-mode': 'strict' };\n\
+             import plain from './plain';",
+        );
+        let imports = import_attributes(&module);
+
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].specifier, "./data.json");
+        assert_eq!(
+            imports[0].attributes,
+            BTreeMap::from([
+                ("type".to_string(), "json".to_string()),
+                ("x-mode".to_string(), "strict".to_string()),
+            ])
+        );
+    }
+}
@@ -1,45 +1,316 @@
+mod assets;
+mod config;
+mod exports;
+mod hoist;
+mod i18n;
+mod imports;
+mod output;
+mod strip_debug;
+
 use std::path::Path;
-use swc::{config::Config, try_with_handler, Compiler};
-use swc_common::{errors::ColorConfig, FileName, SourceMap};
-use swc_ecma_parser::{Parser, StringInput, TsConfig};
-use swc_ecma_transforms::typescript::strip;
+use swc_common::{
+    errors::{ColorConfig, Handler},
+    sync::Lrc,
+    Globals, Mark, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_transforms::{fixer, resolver, typescript::strip};
 use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_ecma_visit::VisitMutWith;
+
+pub use config::BundleConfig;
+pub use i18n::{extract_i18n, Catalog, Location};
+pub use imports::{import_attributes, unused_imports, ImportAttributes, KNOWN_IMPORT_TYPES};
+pub use output::write_bundle;
 
 pub fn bundle(entry: &Path) -> Result<String, Box<dyn std::error::Error>> {
-    let cm = SourceMap::default();
+    bundle_with_config(entry, &BundleConfig::default())
+}
+
+pub fn bundle_with_config(
+    entry: &Path,
+    config: &BundleConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    
+    bundle_with_handler(entry, config, cm, &handler)
+}
+
+fn bundle_with_handler(
+    entry: &Path,
+    config: &BundleConfig,
+    cm: Lrc<SourceMap>,
+    handler: &Handler,
+) -> Result<String, Box<dyn std::error::Error>> {
     let fm = cm.load_file(entry)?;
     
-    let compiler = Compiler::new(cm.clone());
-    let output = try_with_handler(cm.clone(), ColorConfig::Auto, |handler| {
+    GLOBALS.set(&Globals::new(), || {
+        if fm.src.len() as u64 > config.module_size_warning {
+            handler
+                .struct_warn(&format!(
+                    "{} is {} bytes, above the {} byte module size warning threshold",
+                    entry.display(),
+                    fm.src.len(),
+                    config.module_size_warning,
+                ))
+                .emit();
+        }
+        
         let mut parser = Parser::new(
-            TsConfig {
+            Syntax::Typescript(TsConfig {
                 tsx: false,
                 ..Default::default()
-            },
-            StringInput::from(&*fm.src.as_str()),
+            }),
+            StringInput::from(&*fm),
             None,
         );
         
-        let module = parser.parse_module().map_err(|e| e.into_diagnostic(handler).emit())?;
-        
-        let module = compiler.run(|| {
-            strip(module, Default::default())
+        let mut module = parser.parse_module().map_err(|e| {
+            e.into_diagnostic(handler).emit();
+            format!("failed to parse {}", entry.display())
         })?;
         
-        let mut buf = Vec::new();
-        {
-            let mut emitter = Emitter {
-                cfg: swc_ecma_codegen::Config::default(),
-                cm: cm.clone(),
-                comments: None,
-                wr: JsWriter::new(cm, "\n", &mut buf, None),
-            };
-            
-            emitter.emit_module(&module)?;
+        let duplicates = exports::duplicate_exports(&module);
+        for duplicate in &duplicates {
+            handler
+                .struct_span_err(
+                    duplicate.second,
+                    &format!("`{}` is exported more than once", duplicate.name),
+                )
+                .span_label(duplicate.first, "first exported here")
+                .emit();
+        }
+        if !duplicates.is_empty() {
+            return Err(format!("duplicate exports in {}", entry.display()).into());
+        }
+        
+        for unused in imports::unused_imports(&module) {
+            handler
+                .struct_span_warn(
+                    unused.span,
+                    &format!("`{}` is imported but never used", unused.sym),
+                )
+                .emit();
+        }
+        
+        for import in imports::import_attributes(&module) {
+            if let Some(ty) = import.attributes.get("type") {
+                if !imports::KNOWN_IMPORT_TYPES.contains(&ty.as_str()) {
+                    handler
+                        .struct_span_warn(
+                            import.span,
+                            &format!("unknown import type `{}` for `{}`", ty, import.specifier),
+                        )
+                        .emit();
+                }
+            }
         }
         
-        Ok(String::from_utf8(buf)?)
-    })?;
+        if let Some(catalog_path) = &config.i18n_catalog {
+            let functions: Vec<&str> = config.i18n_functions.iter().map(String::as_str).collect();
+            let mut catalog = Catalog::new();
+            i18n::collect_keys(&module, &functions, entry, &cm, &mut catalog);
+            std::fs::write(catalog_path, serde_json::to_string_pretty(&catalog)?)?;
+        }
+        
+        let base_dir = entry.parent().unwrap_or_else(|| Path::new("."));
+        module.visit_mut_with(&mut assets::InlineAssets::new(base_dir, config.inline_asset_limit));
+        
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        module.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, true));
+        module.visit_mut_with(&mut strip(top_level_mark));
+        
+        if config.drop_console || config.drop_debugger {
+            module.visit_mut_with(&mut strip_debug::StripDebug {
+                unresolved_mark,
+                drop_console: config.drop_console,
+                drop_debugger: config.drop_debugger,
+                keep_console: &config.keep_console,
+            });
+        }
+        
+        if let Some(threshold) = config.hoist_constants {
+            hoist::hoist_constants(&mut module, threshold);
+        }
+        
+        // Passes above build expressions without parentheses; restore the
+        // ones the output needs to parse as intended.
+        module.visit_mut_with(&mut fixer(None));
+        
+        emit(cm.clone(), &module)
+    })
+}
+
+fn emit(cm: Lrc<SourceMap>, module: &Module) -> Result<String, Box<dyn std::error::Error>> {
+    let mut buf = Vec::new();
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm, "\n", &mut buf, None),
+        };
+        
+        emitter.emit_module(module)?;
+    }
     
-    Ok(output)
+    Ok(String::from_utf8(buf)?)
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use swc_common::FileName;
+
+    pub fn parse(src: &str) -> Module {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        Parser::new(Syntax::Typescript(Default::default()), StringInput::from(&*fm), None)
+            .parse_module()
+            .expect("test source should parse")
+    }
+
+    pub fn print(module: &Module) -> String {
+        emit(Default::default(), module).unwrap()
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Bundles `src` as `main.ts` and returns the result alongside the
+    /// rendered diagnostics.
+    pub fn bundle_source(src: &str, config: &BundleConfig) -> (Result<String, String>, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("main.ts");
+        std::fs::write(&entry, src).unwrap();
+
+        let cm: Lrc<SourceMap> = Default::default();
+        let buffer = Buffer::default();
+        let handler = Handler::with_emitter_writer(Box::new(buffer.clone()), Some(cm.clone()));
+        let result = bundle_with_handler(&entry, config, cm, &handler).map_err(|e| e.to_string());
+
+        let diagnostics = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        (result, diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::bundle_source;
+
+    #[test]
+    fn duplicate_export_fails_with_both_locations() {
+        let (result, diagnostics) = bundle_source(
+            "export const foo = 1;\nexport function foo() {}\n",
+            &BundleConfig::default(),
+        );
+
+        assert!(result.unwrap_err().starts_with("duplicate exports in"));
+        assert!(diagnostics.contains("`foo` is exported more than once"), "{}", diagnostics);
+        assert!(diagnostics.contains("main.ts:2:17"), "{}", diagnostics);
+        assert!(diagnostics.contains("first exported here"), "{}", diagnostics);
+        assert!(diagnostics.contains("1 | export const foo = 1;"), "{}", diagnostics);
+    }
+
+    #[test]
+    fn writes_i18n_catalog_for_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BundleConfig {
+            i18n_catalog: Some(dir.path().join("catalog.json")),
+            ..BundleConfig::default()
+        };
+        let (result, diagnostics) = bundle_source(
+            "const greeting: string = t(\"greeting\");\nalert(t(\"greeting\"));\n",
+            &config,
+        );
+        assert!(result.is_ok(), "{}", diagnostics);
+
+        let json = std::fs::read_to_string(dir.path().join("catalog.json")).unwrap();
+        let catalog: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let locations: Vec<_> = catalog["greeting"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|loc| (loc["line"].as_u64().unwrap(), loc["column"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(locations, [(1, 28), (2, 9)]);
+        assert!(catalog["greeting"][0]["file"].as_str().unwrap().ends_with("main.ts"));
+    }
+
+    #[test]
+    fn warns_on_unknown_import_type() {
+        let (result, diagnostics) = bundle_source(
+            "import data from './data.json' assert { type: 'json' };\n\
+             import sheet from './theme.scss' assert { type: 'scss' };\n\
+             console.log(data, sheet);\n",
+            &BundleConfig::default(),
+        );
+
+        assert!(result.is_ok(), "{}", diagnostics);
+        assert!(diagnostics.contains("unknown import type `scss` for `./theme.scss`"), "{}", diagnostics);
+        assert!(!diagnostics.contains("`json`"), "{}", diagnostics);
+    }
+
+    #[test]
+    fn warns_only_above_module_size_threshold() {
+        let src = "export const answer = 42;\n";
+        let config = |module_size_warning| BundleConfig {
+            module_size_warning,
+            ..BundleConfig::default()
+        };
+
+        let (_, diagnostics) = bundle_source(src, &config(src.len() as u64));
+        assert!(!diagnostics.contains("module size"), "{}", diagnostics);
+
+        let (_, diagnostics) = bundle_source(src, &config(src.len() as u64 - 1));
+        assert!(
+            diagnostics.contains("is 26 bytes, above the 25 byte module size warning threshold"),
+            "{}",
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn enums_and_namespaces_emit_valid_js() {
+        let (result, diagnostics) = bundle_source(
+            "export enum Color { Red }\n\
+             export namespace Shapes { export const side = 1; }\n\
+             export const y = (Color.Red as any) + 1;\n",
+            &BundleConfig::default(),
+        );
+        let out = result.expect(&diagnostics);
+
+        assert!(out.contains("(function(Color) {"), "{}", out);
+        assert!(out.contains("})(Color || (Color = {}));"), "{}", out);
+        assert!(out.contains("})(Shapes || (Shapes = {}));"), "{}", out);
+        assert!(out.contains("export const y = Color.Red + 1;"), "{}", out);
+        testing::parse(&out);
+    }
+
+    #[test]
+    fn function_overloads_bundle() {
+        let (result, diagnostics) = bundle_source(
+            "export function f(a: string): void;\nexport function f(a: any) { return a; }\n",
+            &BundleConfig::default(),
+        );
+
+        assert!(result.is_ok(), "{}", diagnostics);
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+    }
 }
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes each chunk to `<name>.<hash>.js` under `out_dir` and returns the
+/// logical chunk name → written filename mapping for the manifest. Files that
+/// already exist with identical contents are left untouched. Chunk names must
+/// be plain file names; anything containing a path separator or `..` is
+/// rejected so a chunk cannot be written outside `out_dir`.
+pub fn write_bundle(
+    chunks: &HashMap<String, String>,
+    out_dir: &Path,
+) -> io::Result<HashMap<String, String>> {
+    if let Some(name) = chunks.keys().find(|name| !is_plain_name(name)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid chunk name `{}`", name),
+        ));
+    }
+    fs::create_dir_all(out_dir)?;
+
+    let mut written = HashMap::with_capacity(chunks.len());
+    for (name, code) in chunks {
+        let filename = format!("{}.{:016x}.js", name, content_hash(code.as_bytes()));
+        let path = out_dir.join(&filename);
+
+        let unchanged = match fs::read(&path) {
+            Ok(existing) => existing == code.as_bytes(),
+            Err(_) => false,
+        };
+        if !unchanged {
+            fs::write(&path, code)?;
+        }
+
+        written.insert(name.clone(), filename);
+    }
+
+    Ok(written)
+}
+
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+// FNV-1a: stable across platforms and compiler versions, unlike `DefaultHasher`.
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    fn chunks(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, code)| (name.to_string(), code.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn writes_hashed_filenames_and_returns_mapping() {
+        let dir = tempfile::tempdir().unwrap();
+        let chunks = chunks(&[("main", "console.log(1);"), ("vendor", "export {};")]);
+
+        let written = write_bundle(&chunks, dir.path()).unwrap();
+
+        assert_eq!(written.len(), 2);
+        for (name, code) in &chunks {
+            let filename = &written[name];
+            let expected = format!("{}.{:016x}.js", name, content_hash(code.as_bytes()));
+            assert_eq!(filename, &expected);
+            assert_eq!(
+                &fs::read_to_string(dir.path().join(filename)).unwrap(),
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn skips_identical_existing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let chunks = chunks(&[("main", "console.log(1);")]);
+        let filename = write_bundle(&chunks, dir.path()).unwrap()["main"].clone();
+        let path = dir.path().join(&filename);
+
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let again = write_bundle(&chunks, dir.path()).unwrap();
+
+        assert_eq!(again["main"], filename);
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), old);
+    }
+
+    #[test]
+    fn rejects_names_escaping_out_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["../main", "nested/main", ""] {
+            let err = write_bundle(&chunks(&[(name, "")]), dir.path()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+}
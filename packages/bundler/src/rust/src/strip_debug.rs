@@ -0,0 +1,129 @@
+use swc_common::{Mark, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{VisitMut, VisitMutWith};
+
+/// Removes `console.*(...)` call statements and `debugger` statements. Console
+/// calls used as values (`const log = console.log`, `f(console.log(x))`,
+/// `console.log(x), y`) are left alone, as are methods listed in
+/// `keep_console`. Runs after the resolver: only the global `console` matches,
+/// not a local binding that shadows it.
+pub struct StripDebug<'a> {
+    pub unresolved_mark: Mark,
+    pub drop_console: bool,
+    pub drop_debugger: bool,
+    pub keep_console: &'a [String],
+}
+
+impl StripDebug<'_> {
+    fn is_dropped(&self, stmt: &Stmt) -> bool {
+        match stmt {
+            Stmt::Debugger(_) => self.drop_debugger,
+            Stmt::Expr(expr) if self.drop_console => self.is_console_call(&expr.expr),
+            _ => false,
+        }
+    }
+
+    // Matches `console.log(x)`, `console['log'](x)` and the optional forms
+    // `console.log?.(x)` / `console?.log(x)`.
+    fn is_console_call(&self, expr: &Expr) -> bool {
+        let callee = match expr {
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) => &**callee,
+            Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+                OptChainBase::Call(call) => &*call.callee,
+                OptChainBase::Member(_) => return false,
+            },
+            _ => return false,
+        };
+        let member = match callee {
+            Expr::Member(member) => member,
+            Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+                OptChainBase::Member(member) => member,
+                OptChainBase::Call(_) => return false,
+            },
+            _ => return false,
+        };
+        let method = match &member.prop {
+            MemberProp::Ident(method) => &method.sym,
+            MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+                Expr::Lit(Lit::Str(method)) => &method.value,
+                _ => return false,
+            },
+            MemberProp::PrivateName(_) => return false,
+        };
+        matches!(&*member.obj, Expr::Ident(obj)
+            if &*obj.sym == "console" && obj.span.ctxt.outer() == self.unresolved_mark)
+            && !self.keep_console.iter().any(|keep| keep == &**method)
+    }
+}
+
+impl VisitMut for StripDebug<'_> {
+    // Statements in single-statement positions (`if (x) debugger;`) become
+    // empty statements; those in lists are removed outright below.
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        if self.is_dropped(stmt) {
+            *stmt = Stmt::Empty(EmptyStmt { span: DUMMY_SP });
+            return;
+        }
+        stmt.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        stmts.retain(|stmt| !self.is_dropped(stmt));
+        stmts.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.retain(|item| !matches!(item, ModuleItem::Stmt(stmt) if self.is_dropped(stmt)));
+        items.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{parse, print};
+    use swc_common::{Globals, GLOBALS};
+    use swc_ecma_transforms::resolver;
+
+    fn strip(src: &str) -> String {
+        let keep_console = vec!["error".to_string(), "warn".to_string()];
+        let mut module = parse(src);
+        GLOBALS.set(&Globals::new(), || {
+            let unresolved_mark = Mark::new();
+            module.visit_mut_with(&mut resolver(unresolved_mark, Mark::new(), true));
+            module.visit_mut_with(&mut StripDebug {
+                unresolved_mark,
+                drop_console: true,
+                drop_debugger: true,
+                keep_console: &keep_console,
+            });
+        });
+        print(&module)
+    }
+
+    #[test]
+    fn removes_console_call_statements() {
+        let src = "console.log(x);\nconsole.log?.(1);\nconsole?.info(2);\nconsole['log'](3);\nf();";
+        assert_eq!(strip(src), "f();\n");
+    }
+
+    #[test]
+    fn keeps_listed_methods_and_console_values() {
+        let src = "console.error(x);\nconsole['warn']?.(x);\nconst log = console.log;\n";
+        assert_eq!(strip(src), src);
+    }
+
+    #[test]
+    fn empties_single_statement_debugger() {
+        assert_eq!(strip("if (x) debugger;"), "if (x) ;\n");
+    }
+
+    #[test]
+    fn keeps_calls_on_shadowed_console() {
+        let out = strip("function f(console) {\n    console.log(x);\n}");
+        assert!(out.contains("console.log(x)"), "{}", out);
+    }
+}
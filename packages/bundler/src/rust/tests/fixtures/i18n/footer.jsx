@@ -0,0 +1,4 @@
+export function Footer() {
+  return <footer>{i18n.translate("footer.copyright")}</footer>;
+}
+const close = t("shared.close");
@@ -0,0 +1,4 @@
+import { t } from "./i18n";
+
+export const title: string = t("home.title");
+log(t(`shared.close`), t(dynamicKey), other("home.ignored"));
@@ -0,0 +1,6 @@
+import { i18n } from "./i18n";
+
+export function Menu(props: { open: boolean }) {
+  const label = t("menu.open");
+  return <button title={i18n.translate("shared.close")}>{label}</button>;
+}